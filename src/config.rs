@@ -0,0 +1,170 @@
+//! Explicit configuration for [`crate::init_tracing_with`], for callers that build up their
+//! logging setup from a CLI or TOML config rather than relying solely on process environment
+//! variables.
+//!
+//! `RUST_LOG` / `LOG_FORMAT`, when set, still take precedence over the corresponding
+//! [`LoggerConfig`] fields, so existing `init_tracing()` callers keep their current behavior.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Output format for the `fmt` layer (or `journald`, which bypasses it entirely).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Compact,
+    Json,
+    Journald,
+}
+
+impl LogFormat {
+    /// Parses a `LOG_FORMAT`-style value, returning `None` for anything unrecognized so the
+    /// caller can fall back to its own default instead of silently picking one.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("compact") {
+            Some(LogFormat::Compact)
+        } else if value.eq_ignore_ascii_case("json") {
+            Some(LogFormat::Json)
+        } else if value.eq_ignore_ascii_case("journald") {
+            Some(LogFormat::Journald)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Compact => "compact",
+            LogFormat::Json => "json",
+            LogFormat::Journald => "journald",
+        }
+    }
+}
+
+/// Where formatted log output is written. Ignored while [`LogFormat::Journald`] output is in
+/// use, since that goes to the systemd journal socket instead; when journald falls back to
+/// compact logging, the fallback output is written here.
+#[derive(Debug, Clone)]
+pub enum OutputTarget {
+    Stdout,
+    Stderr,
+    File(PathBuf),
+}
+
+/// Configuration accepted by [`crate::init_tracing_with`].
+///
+/// Build one with [`LoggerConfig::default`] and the `with_*` builder methods, then pass it to
+/// [`crate::init_tracing_with`]. `init_tracing()` is a thin wrapper around
+/// `init_tracing_with(LoggerConfig::default())`.
+#[derive(Debug, Clone)]
+pub struct LoggerConfig {
+    pub level: String,
+    pub format: LogFormat,
+    pub install_panic_hook: bool,
+    pub enable_log_bridge: bool,
+    pub output: OutputTarget,
+}
+
+impl Default for LoggerConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: LogFormat::Compact,
+            install_panic_hook: true,
+            enable_log_bridge: true,
+            output: OutputTarget::Stdout,
+        }
+    }
+}
+
+impl LoggerConfig {
+    /// Equivalent to [`LoggerConfig::default`], provided for builder-style call sites.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default log level, used when `RUST_LOG` is not set in the environment.
+    pub fn with_level(mut self, level: impl Into<String>) -> Self {
+        self.level = level.into();
+        self
+    }
+
+    /// Sets the output format, overridden by `LOG_FORMAT` when that's set in the environment to
+    /// `compact`, `json` or `journald`; any other `LOG_FORMAT` value leaves this format in place.
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Controls whether `init_tracing_with` installs the panicking-logs-a-structured-event
+    /// hook. Defaults to `true`.
+    pub fn with_panic_hook(mut self, install_panic_hook: bool) -> Self {
+        self.install_panic_hook = install_panic_hook;
+        self
+    }
+
+    /// Controls whether the `log` crate facade is bridged into tracing. Defaults to `true`.
+    pub fn with_log_bridge(mut self, enable_log_bridge: bool) -> Self {
+        self.enable_log_bridge = enable_log_bridge;
+        self
+    }
+
+    /// Sets where formatted output is written. Defaults to stdout.
+    pub fn with_output(mut self, output: OutputTarget) -> Self {
+        self.output = output;
+        self
+    }
+}
+
+/// A file handle shared (and locked per-write) across every `MakeWriter` call.
+#[derive(Clone)]
+pub(crate) struct SharedFile(Arc<Mutex<fs::File>>);
+
+impl io::Write for SharedFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).flush()
+    }
+}
+
+/// The concrete writer produced from an [`OutputTarget`] for the `fmt` layer to write into.
+#[derive(Clone)]
+pub(crate) enum ConfiguredWriter {
+    Stdout,
+    Stderr,
+    File(SharedFile),
+}
+
+impl<'a> MakeWriter<'a> for ConfiguredWriter {
+    type Writer = Box<dyn io::Write>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        match self {
+            ConfiguredWriter::Stdout => Box::new(io::stdout()),
+            ConfiguredWriter::Stderr => Box::new(io::stderr()),
+            ConfiguredWriter::File(file) => Box::new(file.clone()),
+        }
+    }
+}
+
+/// Resolves an [`OutputTarget`] into a [`ConfiguredWriter`], falling back to stderr (with a
+/// warning) if a configured log file can't be opened.
+pub(crate) fn build_writer(output: &OutputTarget) -> ConfiguredWriter {
+    match output {
+        OutputTarget::Stdout => ConfiguredWriter::Stdout,
+        OutputTarget::Stderr => ConfiguredWriter::Stderr,
+        OutputTarget::File(path) => match fs::OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => ConfiguredWriter::File(SharedFile(Arc::new(Mutex::new(file)))),
+            Err(err) => {
+                eprintln!("Failed to open log file {}: {err}; falling back to stderr logging", path.display());
+                ConfiguredWriter::Stderr
+            }
+        },
+    }
+}
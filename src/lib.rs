@@ -52,7 +52,8 @@
 //!
 //! ### Output Format
 //!
-//! Switch between compact and JSON output using the `LOG_FORMAT` environment variable:
+//! Switch between compact, JSON and systemd-journald output using the `LOG_FORMAT`
+//! environment variable:
 //!
 //! ```bash
 //! # Compact format (default)
@@ -60,8 +61,18 @@
 //!
 //! # JSON format
 //! LOG_FORMAT=json cargo run
+//!
+//! # systemd-journald format
+//! LOG_FORMAT=journald cargo run
 //! ```
 //!
+//! `LOG_FORMAT=journald` attaches a [`tracing-journald`](https://docs.rs/tracing-journald)
+//! layer instead of the stdout `fmt` layer, so tracing levels are mapped to journald
+//! priorities and span fields are forwarded as journal fields natively. If
+//! `/run/systemd/journal/socket` isn't reachable (e.g. in a container or during local
+//! development), initialization falls back to compact stdout output and emits a warning
+//! instead of failing.
+//!
 //! ## Thread Safety
 //!
 //! This library is designed to be thread-safe and can be safely called from multiple threads.
@@ -74,17 +85,91 @@
 //! - Efficient string formatting and serialization
 //! - Minimal memory allocations during normal operation
 //!
+//! ## Flight Recorder
+//!
+//! An opt-in ring buffer can retain the last `N` trace-level events in memory regardless of
+//! the `RUST_LOG` filter governing stdout, and dump them to stderr on panic:
+//!
+//! ```bash
+//! # Keep the last 500 events (trace level and above) and dump them on panic
+//! LOG_RECORDER_CAPACITY=500 cargo run
+//!
+//! # Only capture debug level and above into the buffer
+//! LOG_RECORDER_CAPACITY=500 LOG_RECORDER_LEVEL=debug cargo run
+//! ```
+//!
+//! It defaults to off (`LOG_RECORDER_CAPACITY=0`) so there's no overhead unless opted into.
+//!
+//! ## Bridging the `log` Crate
+//!
+//! Many dependencies emit through the [`log`](https://docs.rs/log) facade rather than
+//! [`tracing`]. `init_tracing()` installs a [`tracing_log::LogTracer`] so those records are
+//! converted into tracing events and flow through the same `RUST_LOG` filter and format
+//! layer as everything else - no extra configuration needed.
+//!
+//! ## Configuring Programmatically
+//!
+//! Applications that build their configuration from a CLI or TOML file rather than process
+//! env vars can use [`LoggerConfig`] and [`init_tracing_with`] instead of the env-var-only
+//! `init_tracing()`:
+//!
+//! ```rust
+//! use ds_common_logger_rs_lib::{init_tracing_with, LogFormat, LoggerConfig};
+//!
+//! init_tracing_with(
+//!     LoggerConfig::default()
+//!         .with_level("debug")
+//!         .with_format(LogFormat::Json),
+//! );
+//! ```
+//!
+//! `RUST_LOG` and `LOG_FORMAT`, when set in the environment, still override the
+//! corresponding `LoggerConfig` fields, so existing deployments keep working unchanged.
+//!
+//! ## Scoped Contextual Fields
+//!
+//! [`with_scope`] (and its async-aware sibling [`with_scope_async`]) attach a set of
+//! key-value pairs to every event logged within a closure or future, so call sites don't need
+//! to repeat things like `request_id` or `tenant` on every log statement:
+//!
+//! ```rust
+//! use ds_common_logger_rs_lib::with_scope;
+//!
+//! with_scope("request", &[("request_id", "abc123"), ("tenant", "acme")], || {
+//!     tracing::info!("handling request");
+//! });
+//! ```
+//!
+//! [`enter_scope`] returns a guard for callers that need to enter and exit the scope manually
+//! rather than wrapping a single closure.
+//!
 use std::sync::Once;
 use tracing::{error, info};
 use tracing_error::ErrorLayer;
+use tracing_log::LogTracer;
 use tracing_subscriber::{
-    filter::EnvFilter,
+    filter::{EnvFilter, LevelFilter},
     fmt::{self, format::FmtSpan},
     prelude::*,
     registry::Registry,
     util::SubscriberInitExt,
 };
 
+mod config;
+mod recorder;
+mod scope;
+
+pub use config::{LogFormat, LoggerConfig, OutputTarget};
+pub use scope::{enter_scope, with_scope, with_scope_async, ScopeGuard};
+
+/// Returns a snapshot of the flight recorder's current contents, oldest-to-newest, without
+/// draining it. Returns an empty vector if the recorder isn't enabled (`LOG_RECORDER_CAPACITY`
+/// unset or `0`). Useful for tests, or for exposing recent log history through an admin
+/// endpoint, without waiting for a panic to dump it.
+pub fn flight_recorder_snapshot() -> Vec<String> {
+    recorder::snapshot()
+}
+
 static INIT: Once = Once::new();
 
 /// # Logger Module
@@ -106,7 +191,14 @@ static INIT: Once = Once::new();
 /// # Environment Variables
 ///
 /// - `RUST_LOG`: Controls log level filtering (e.g., `info`, `debug`, `error`)
-/// - `LOG_FORMAT`: Controls output format (`json` for JSON format, anything else for compact)
+/// - `LOG_FORMAT`: Controls output format (`json` for JSON, `journald` for systemd-journald,
+///   anything else for compact)
+/// - `LOG_RECORDER_CAPACITY`: Enables the flight recorder ring buffer with this many retained
+///   events (disabled when `0` or unset)
+/// - `LOG_RECORDER_LEVEL`: Finest level captured into the flight recorder (defaults to `trace`)
+///
+/// Records emitted through the [`log`] crate by third-party dependencies are bridged into
+/// this same subscriber via [`tracing_log::LogTracer`], so they respect `RUST_LOG` too.
 ///
 /// # Thread Safety
 ///
@@ -137,7 +229,7 @@ static INIT: Once = Once::new();
 ///
 /// In tests:
 ///
-/// ```rust
+/// ```rust,ignore
 /// #[cfg(test)]
 /// mod tests {
 ///     use super::*;
@@ -150,62 +242,158 @@ static INIT: Once = Once::new();
 /// }
 /// ```
 pub fn init_tracing() {
+    init_tracing_with(LoggerConfig::default());
+}
+
+/// Initializes a global [`tracing`] subscriber exactly **once**, from an explicit
+/// [`LoggerConfig`] rather than process environment variables alone.
+///
+/// `init_tracing()` is a thin wrapper around `init_tracing_with(LoggerConfig::default())`; use
+/// this function directly when your application builds its logging configuration from its own
+/// CLI flags or config file. `RUST_LOG` and `LOG_FORMAT`, when set in the environment, still
+/// override `config.level` and `config.format` respectively, so the two entry points compose:
+/// an env var set at deploy time always wins over whatever the binary hard-codes.
+///
+/// Like [`init_tracing`], this is idempotent - only the first call (whichever entry point it
+/// comes through) takes effect.
+///
+/// # Examples
+///
+/// ```rust
+/// use ds_common_logger_rs_lib::{init_tracing_with, LogFormat, LoggerConfig};
+///
+/// init_tracing_with(
+///     LoggerConfig::default()
+///         .with_level("debug")
+///         .with_format(LogFormat::Json)
+///         .with_log_bridge(false),
+/// );
+/// ```
+pub fn init_tracing_with(config: LoggerConfig) {
     INIT.call_once(|| {
-        // 1. Filtering via env
-        let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-
-        // 2. Decide on format layer
-        let use_json = std::env::var("LOG_FORMAT")
-            .map(|v| v.eq_ignore_ascii_case("json"))
-            .unwrap_or(false);
-
-        // Base registry
-        let base = Registry::default().with(env_filter).with(ErrorLayer::default());
-
-        // 4. Attach stdout layer + init
-        if use_json {
-            base.with(
-                fmt::layer()
-                    .json()
-                    .with_target(true)
-                    .with_thread_ids(true)
-                    .with_thread_names(true)
-                    .with_current_span(true)
-                    .with_span_list(true)
-                    .with_span_events(FmtSpan::CLOSE),
-            )
-            .init();
-        } else {
-            base.with(
-                fmt::layer()
-                    .compact()
-                    .with_target(true)
-                    .with_thread_ids(true)
-                    .with_thread_names(true)
-                    .with_span_events(FmtSpan::CLOSE),
-            )
-            .init();
+        // 0. Bridge the `log` facade into tracing so third-party crates using `log` are
+        //    captured by the same subscriber and filter.
+        if config.enable_log_bridge {
+            if let Err(err) = LogTracer::init() {
+                eprintln!("Failed to install log-to-tracing bridge: {err}");
+            }
+        }
+
+        // 1. Filtering via env, falling back to the configured default level.
+        let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.level));
+
+        // 2. Decide on format layer: LOG_FORMAT overrides the configured format.
+        let format = std::env::var("LOG_FORMAT")
+            .ok()
+            .and_then(|v| LogFormat::parse(&v))
+            .unwrap_or(config.format);
+
+        // 3. Resolve where formatted output goes (ignored for journald).
+        let writer = config::build_writer(&config.output);
+
+        // Base registry. Note `env_filter` is NOT attached here: a filter applied directly to
+        // the registry acts globally, collapsing callsite `Interest` for every layer (including
+        // the flight recorder) down to the `RUST_LOG` threshold. Instead it's attached per-layer
+        // below, to only the format layer, so the recorder's own independent `LevelFilter` still
+        // sees events the format layer would drop.
+        //
+        // `ErrorLayer` itself carries no filter and would otherwise pin the subscriber's global
+        // max-level hint to `TRACE` unconditionally, formatting every `trace!`/`debug!` call's
+        // arguments process-wide even with the recorder off - defeating the "zero-cost when
+        // disabled" goal. Bound it to whichever of `env_filter` or the recorder's level is more
+        // permissive, so a disabled recorder doesn't widen the hint beyond what `RUST_LOG`
+        // already requires.
+        let (recorder_layer, recorder_level_hint) = recorder::build_recorder_layer();
+        let error_layer_level_hint = env_filter.max_level_hint().unwrap_or(LevelFilter::TRACE).max(recorder_level_hint);
+        let base = Registry::default()
+            .with(ErrorLayer::default().with_filter(error_layer_level_hint))
+            .with(recorder_layer);
+
+        // 4. Attach the format layer + init. `tracing-subscriber`'s default `tracing-log`
+        //    feature is disabled (see Cargo.toml) specifically so this crate is the sole owner
+        //    of the `log` bridge installed in step 0 - otherwise `init()`/`try_init()` would
+        //    attempt a second, redundant `LogTracer::init()` of its own, which both defeats
+        //    `enable_log_bridge(false)` and surfaces a spurious "logger already set" error.
+        match format {
+            LogFormat::Json => {
+                base.with(
+                    fmt::layer()
+                        .json()
+                        .with_writer(writer)
+                        .with_target(true)
+                        .with_thread_ids(true)
+                        .with_thread_names(true)
+                        .with_current_span(true)
+                        .with_span_list(true)
+                        .with_span_events(FmtSpan::CLOSE)
+                        .with_filter(env_filter),
+                )
+                .init();
+            }
+            LogFormat::Journald if journald_socket_available() => match tracing_journald::layer() {
+                Ok(layer) => base.with(layer.with_filter(env_filter)).init(),
+                Err(err) => {
+                    eprintln!("LOG_FORMAT=journald requested but the journald layer could not be created ({err}); falling back to compact logging");
+                    base.with(compact_layer(writer, env_filter)).init();
+                }
+            },
+            LogFormat::Journald => {
+                eprintln!(
+                    "LOG_FORMAT=journald requested but /run/systemd/journal/socket is unavailable; falling back to compact logging"
+                );
+                base.with(compact_layer(writer, env_filter)).init();
+            }
+            LogFormat::Compact => {
+                base.with(compact_layer(writer, env_filter)).init();
+            }
         }
 
         // 5. Log panics
-        std::panic::set_hook(Box::new(|panic_info| {
-            let location = panic_info
-                .location()
-                .map(|l| format!("{}:{}", l.file(), l.line()))
-                .unwrap_or_else(|| "unknown".to_string());
-
-            error!(
-                %location,
-                payload = %panic_info.to_string(),
-                "Application panicked"
-            );
-        }));
+        if config.install_panic_hook {
+            std::panic::set_hook(Box::new(|panic_info| {
+                let location = panic_info
+                    .location()
+                    .map(|l| format!("{}:{}", l.file(), l.line()))
+                    .unwrap_or_else(|| "unknown".to_string());
+
+                error!(
+                    %location,
+                    payload = %panic_info.to_string(),
+                    "Application panicked"
+                );
+
+                recorder::flush_to_stderr();
+            }));
+        }
 
         info!(
-            format = %if use_json { "json" } else { "compact" },
+            format = %format.as_str(),
             "Tracing initialized"
         );
     });
 }
 
+/// Returns `true` when the systemd journal socket is reachable, i.e. when it's safe to
+/// attempt a [`tracing_journald`] layer.
+fn journald_socket_available() -> bool {
+    std::path::Path::new("/run/systemd/journal/socket").exists()
+}
+
+/// Builds the compact `fmt` layer shared by the compact format and the journald fallback
+/// path, writing to whichever sink the caller configured and filtered independently by
+/// `filter` so it doesn't affect what other layers (e.g. the flight recorder) observe.
+fn compact_layer<S>(writer: config::ConfiguredWriter, filter: EnvFilter) -> impl tracing_subscriber::Layer<S>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fmt::layer()
+        .compact()
+        .with_writer(writer)
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_thread_names(true)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_filter(filter)
+}
+
 // endregion: <-- Logger
@@ -0,0 +1,192 @@
+//! Flight recorder: an in-memory ring buffer of recent log events.
+//!
+//! Normal output is governed by the `RUST_LOG` filter passed to [`crate::init_tracing`], but
+//! that means fine-grained detail is discarded once it scrolls past. This module adds an
+//! opt-in [`Layer`] that always records the last `N` events at a configurable level into a
+//! fixed-size circular buffer, independent of the stdout filter, so the detail is still
+//! around to dump when a panic happens.
+//!
+//! Enable it via environment variables:
+//!
+//! - `LOG_RECORDER_CAPACITY`: number of events to retain (`0`, the default, disables the
+//!   recorder entirely so there is no overhead when it's unused).
+//! - `LOG_RECORDER_LEVEL`: the finest level captured into the buffer (defaults to `trace`).
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Handle to the live ring buffer, stashed here so the panic hook can reach it without
+/// threading state through `std::panic::set_hook`.
+static BUFFER_HANDLE: OnceLock<Arc<Mutex<VecDeque<String>>>> = OnceLock::new();
+
+/// Per-span fields captured on `on_new_span`, stored in the span's extensions so `on_event`
+/// can stitch them back together for every event emitted underneath that span.
+struct SpanFields(Vec<(String, String)>);
+
+#[derive(Default)]
+struct EventVisitor {
+    message: Option<String>,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for EventVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        } else {
+            self.fields.push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+}
+
+/// A `tracing_subscriber` [`Layer`] that serializes every event it sees into a pre-allocated
+/// circular buffer, overwriting the oldest entry once `capacity` is reached.
+pub(crate) struct FlightRecorderLayer {
+    buffer: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl FlightRecorderLayer {
+    fn push(&self, entry: String) {
+        let mut buffer = self.buffer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if buffer.len() >= self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(entry);
+    }
+}
+
+impl<S> Layer<S> for FlightRecorderLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = EventVisitor::default();
+        attrs.record(&mut visitor);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+
+        let mut span_fields = Vec::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    span_fields.extend(fields.iter().cloned());
+                }
+            }
+        }
+
+        let metadata = event.metadata();
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        let mut entry = format!(
+            "{}.{:06} {} {} message={:?}",
+            timestamp.as_secs(),
+            timestamp.subsec_micros(),
+            metadata.level(),
+            metadata.target(),
+            visitor.message.unwrap_or_default(),
+        );
+
+        for (key, value) in span_fields.iter().chain(visitor.fields.iter()) {
+            entry.push_str(&format!(" {key}={value}"));
+        }
+
+        self.push(entry);
+    }
+}
+
+/// Builds the flight-recorder layer from `LOG_RECORDER_CAPACITY` / `LOG_RECORDER_LEVEL`,
+/// already wrapped in its own [`LevelFilter`] so it captures independently of whatever
+/// `EnvFilter` governs stdout, plus that same [`LevelFilter`] on its own (or [`LevelFilter::OFF`]
+/// when disabled) for the caller to fold into the subscriber's overall max-level hint - see
+/// [`crate::init_tracing_with`]. Returns `None` for the layer itself (zero-cost) when the
+/// capacity is `0` or unset.
+pub(crate) fn build_recorder_layer<S>() -> (Option<impl Layer<S>>, LevelFilter)
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let capacity: usize = std::env::var("LOG_RECORDER_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    if capacity == 0 {
+        return (None, LevelFilter::OFF);
+    }
+
+    let level: Level = std::env::var("LOG_RECORDER_LEVEL")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(Level::TRACE);
+    let level_filter = LevelFilter::from_level(level);
+
+    let buffer = Arc::new(Mutex::new(VecDeque::with_capacity(capacity)));
+    let _ = BUFFER_HANDLE.set(buffer.clone());
+
+    (Some(FlightRecorderLayer { buffer, capacity }.with_filter(level_filter)), level_filter)
+}
+
+/// Drains the ring buffer oldest-to-newest and writes each entry to stderr. Called from the
+/// panic hook in addition to the usual structured `error!` so recent detail isn't lost.
+pub(crate) fn flush_to_stderr() {
+    let Some(buffer) = BUFFER_HANDLE.get() else {
+        return;
+    };
+
+    let mut buffer = buffer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if buffer.is_empty() {
+        return;
+    }
+
+    eprintln!("---- flight recorder: last {} buffered event(s) ----", buffer.len());
+    for entry in buffer.drain(..) {
+        eprintln!("{entry}");
+    }
+    eprintln!("---- end flight recorder dump ----");
+}
+
+/// Returns a snapshot of the ring buffer's current contents, oldest-to-newest, without
+/// draining it. Returns an empty vector when the recorder isn't enabled.
+pub(crate) fn snapshot() -> Vec<String> {
+    let Some(buffer) = BUFFER_HANDLE.get() else {
+        return Vec::new();
+    };
+
+    buffer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest_entry_once_capacity_is_reached() {
+        let layer = FlightRecorderLayer {
+            buffer: Arc::new(Mutex::new(VecDeque::new())),
+            capacity: 2,
+        };
+
+        layer.push("first".to_string());
+        layer.push("second".to_string());
+        layer.push("third".to_string());
+
+        let remaining: Vec<_> = layer.buffer.lock().unwrap().iter().cloned().collect();
+        assert_eq!(remaining, vec!["second".to_string(), "third".to_string()]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Scoped contextual key-value fields attached to every log event within a scope.
+//!
+//! Tracing's span macros require field names known at compile time, so a genuinely dynamic
+//! set of key-value pairs (e.g. a `request_id` read from an incoming request) can't become
+//! individual span fields. Instead, the scope opens a single `scope` span carrying the scope's
+//! `name` plus a `fields` value that renders the key-value pairs as `key=value, key=value`,
+//! which still flows through `with_current_span`/`with_span_list` in the JSON layer configured
+//! by [`crate::init_tracing`] like any other span field.
+//!
+//! The scope span is created at [`tracing::Level::ERROR`] rather than the more natural `INFO`
+//! so it's still entered (and its fields still attach to whatever events the caller's
+//! `RUST_LOG` lets through) even when the filter is tightened to `warn` or `error` in
+//! production - the span's own level is otherwise unrelated to the level of the events
+//! emitted inside it.
+//!
+//! **Representation limitation:** downstream JSON consumers (the `json` [`crate::LogFormat`])
+//! see this as `{"name":"request","fields":"request_id=\"abc123\", ...","name":"scope"}` (the
+//! span's `name` field and the span's own name share the `name` key) rather than
+//! `{"request_id":"abc123", ...}` as individually queryable keys - the `fields`
+//! value is one opaque, Debug-formatted string, not structured JSON. A consumer that wants to
+//! filter or aggregate on `request_id` will need to parse that string itself. Making each `kvs`
+//! entry a real span field would require per-callsite static field names (which `kvs` being
+//! dynamic rules out) or a dependency on [`valuable`](https://docs.rs/valuable) for genuinely
+//! dynamic structured fields; neither is done here to keep this crate's dependency surface
+//! small.
+
+use std::fmt;
+use std::future::Future;
+
+use tracing::span::EnteredSpan;
+use tracing::Instrument;
+
+struct ScopeFields<'a>(&'a [(&'a str, &'a str)]);
+
+impl fmt::Debug for ScopeFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}={value:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Guard returned by [`enter_scope`]. The scope stays attached to the current thread until
+/// this guard is dropped.
+pub struct ScopeGuard {
+    _entered: EnteredSpan,
+}
+
+/// Opens a scope named `name` carrying `kvs` and enters it for the current thread, returning
+/// a guard that keeps the scope active until dropped.
+///
+/// Prefer [`with_scope`] (or [`with_scope_async`]) when the scope should only cover a single
+/// closure or future; use this form when entry and exit happen at different points, e.g.
+/// across a struct's lifetime.
+///
+/// # Examples
+///
+/// ```rust
+/// use ds_common_logger_rs_lib::enter_scope;
+///
+/// let _scope = enter_scope("request", &[("request_id", "abc123"), ("tenant", "acme")]);
+/// tracing::info!("handling request");
+/// ```
+pub fn enter_scope(name: &str, kvs: &[(&str, &str)]) -> ScopeGuard {
+    let span = tracing::error_span!("scope", name = %name, fields = ?ScopeFields(kvs));
+    ScopeGuard { _entered: span.entered() }
+}
+
+/// Runs `f` with `kvs` attached to every event logged inside it, via a span named `name` that
+/// is entered for the duration of the call.
+///
+/// # Examples
+///
+/// ```rust
+/// use ds_common_logger_rs_lib::with_scope;
+///
+/// let result = with_scope("request", &[("request_id", "abc123")], || {
+///     tracing::info!("handling request");
+///     42
+/// });
+/// assert_eq!(result, 42);
+/// ```
+pub fn with_scope<R>(name: &str, kvs: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+    let _scope = enter_scope(name, kvs);
+    f()
+}
+
+/// Async-aware variant of [`with_scope`]: instruments `fut` with a span carrying `kvs` so the
+/// fields survive across `.await` points, unlike a plain [`enter_scope`] guard held across an
+/// await (which would incorrectly stay entered while other tasks run on the same thread).
+///
+/// # Examples
+///
+/// ```rust
+/// use ds_common_logger_rs_lib::with_scope_async;
+///
+/// # async fn handle_request() {}
+/// # async fn example() {
+/// with_scope_async("request", &[("request_id", "abc123")], handle_request()).await;
+/// # }
+/// ```
+pub async fn with_scope_async<F>(name: &str, kvs: &[(&str, &str)], fut: F) -> F::Output
+where
+    F: Future,
+{
+    let span = tracing::error_span!("scope", name = %name, fields = ?ScopeFields(kvs));
+    fut.instrument(span).await
+}
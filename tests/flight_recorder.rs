@@ -0,0 +1,35 @@
+use ds_common_logger_rs_lib::{flight_recorder_snapshot, init_tracing};
+use std::panic;
+
+// Single test function: the recorder's capacity/level are read from the environment only on
+// the very first `init_tracing()` call in the whole process (guarded by an internal `Once`),
+// so the env vars below must be set before anything else in this binary can call it first.
+#[test]
+fn flight_recorder_captures_below_threshold_and_drains_on_panic() {
+    std::env::set_var("RUST_LOG", "info");
+    std::env::set_var("LOG_RECORDER_CAPACITY", "50");
+    std::env::set_var("LOG_RECORDER_LEVEL", "trace");
+
+    init_tracing();
+
+    tracing::trace!("flight recorder trace probe");
+    tracing::debug!("flight recorder debug probe");
+
+    let snapshot = flight_recorder_snapshot();
+    assert!(
+        snapshot.iter().any(|entry| entry.contains("flight recorder trace probe")),
+        "expected a trace-level event to be captured despite RUST_LOG=info, got: {snapshot:?}"
+    );
+    assert!(
+        snapshot.iter().any(|entry| entry.contains("flight recorder debug probe")),
+        "expected a debug-level event to be captured despite RUST_LOG=info, got: {snapshot:?}"
+    );
+
+    let result = panic::catch_unwind(|| {
+        panic!("trigger flight recorder dump");
+    });
+    assert!(result.is_err());
+
+    let snapshot = flight_recorder_snapshot();
+    assert!(snapshot.is_empty(), "expected the panic hook to drain the buffer, got: {snapshot:?}");
+}
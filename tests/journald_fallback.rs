@@ -0,0 +1,11 @@
+use ds_common_logger_rs_lib::{init_tracing_with, LogFormat, LoggerConfig};
+
+// This sandbox has no systemd journal socket, so requesting `Journald` must fall back to
+// compact logging (with a warning on stderr) instead of panicking or silently dropping logs.
+#[test]
+fn journald_without_a_socket_falls_back_to_compact_logging() {
+    init_tracing_with(LoggerConfig::default().with_format(LogFormat::Journald));
+
+    // Should not panic, and logging through the compact fallback should still work.
+    tracing::info!("journald fallback probe");
+}
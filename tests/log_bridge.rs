@@ -0,0 +1,25 @@
+use ds_common_logger_rs_lib::init_tracing;
+use std::thread;
+
+#[test]
+fn log_bridge_is_idempotent_under_concurrent_init() {
+    // Several threads racing to initialize must not panic or double-install the `log` facade
+    // logger (`LogTracer::init()` errors on a second call, which `init_tracing` already
+    // swallows - the `Once` guard means it's only ever attempted once regardless).
+    let handles: Vec<_> = (0..10)
+        .map(|i| {
+            thread::spawn(move || {
+                init_tracing();
+                log::info!("log crate record from thread {i}");
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // The bridge should still be usable from the main thread afterwards.
+    log::warn!("log crate record after concurrent init");
+    tracing::info!("tracing record alongside the log crate bridge");
+}
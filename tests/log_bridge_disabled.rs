@@ -0,0 +1,25 @@
+use ds_common_logger_rs_lib::{flight_recorder_snapshot, init_tracing_with, LoggerConfig};
+
+// With the bridge disabled, `log` records never reach the `tracing` subscriber at all. Verify
+// via the flight recorder (which otherwise captures every event regardless of `RUST_LOG`) that a
+// `log::info!` call is silently dropped while a plain `tracing::info!` call still comes through.
+#[test]
+fn with_log_bridge_false_drops_log_crate_records() {
+    std::env::set_var("LOG_RECORDER_CAPACITY", "50");
+    std::env::set_var("LOG_RECORDER_LEVEL", "trace");
+
+    init_tracing_with(LoggerConfig::default().with_log_bridge(false));
+
+    log::info!("log crate record that should be dropped");
+    tracing::info!("tracing record that should still be captured");
+
+    let snapshot = flight_recorder_snapshot();
+    assert!(
+        !snapshot.iter().any(|entry| entry.contains("log crate record that should be dropped")),
+        "expected log::info! to be dropped with the bridge disabled, got: {snapshot:?}"
+    );
+    assert!(
+        snapshot.iter().any(|entry| entry.contains("tracing record that should still be captured")),
+        "expected tracing::info! to still be captured, got: {snapshot:?}"
+    );
+}
@@ -0,0 +1,37 @@
+use std::fs;
+use std::io::Read;
+
+use ds_common_logger_rs_lib::{init_tracing_with, LogFormat, LoggerConfig};
+
+// Single test: the first `init_tracing_with` call in this binary decides the process-wide
+// subscriber, so both overrides under test must be proven from that one call. Logging to a
+// temp file (rather than stdout) lets the test read back what was actually emitted.
+#[test]
+fn env_vars_override_configured_level_and_format() {
+    let path = std::env::temp_dir().join(format!("ds-common-logger-precedence-{}.log", std::process::id()));
+
+    std::env::set_var("RUST_LOG", "debug");
+    std::env::set_var("LOG_FORMAT", "json");
+
+    init_tracing_with(
+        LoggerConfig::default()
+            .with_level("error")
+            .with_format(LogFormat::Compact)
+            .with_output(ds_common_logger_rs_lib::OutputTarget::File(path.clone())),
+    );
+
+    tracing::debug!("precedence debug probe");
+
+    let mut contents = String::new();
+    fs::File::open(&path).expect("log file should have been created").read_to_string(&mut contents).unwrap();
+    let _ = fs::remove_file(&path);
+
+    assert!(
+        contents.contains("precedence debug probe"),
+        "expected RUST_LOG=debug to override the configured \"error\" level, got: {contents:?}"
+    );
+    assert!(
+        contents.lines().any(|line| line.trim_start().starts_with('{')),
+        "expected LOG_FORMAT=json to override the configured Compact format, got: {contents:?}"
+    );
+}
@@ -0,0 +1,97 @@
+use std::sync::{Arc, Mutex};
+
+use ds_common_logger_rs_lib::{with_scope, with_scope_async};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::Layer;
+
+#[derive(Default)]
+struct CapturedSpan {
+    span_name: &'static str,
+    name_field: Option<String>,
+    fields_field: Option<String>,
+}
+
+#[derive(Default, Clone)]
+struct Captor(Arc<Mutex<Vec<CapturedSpan>>>);
+
+#[derive(Default)]
+struct FieldVisitor {
+    name_field: Option<String>,
+    fields_field: Option<String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.name_field = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "name" => self.name_field = Some(format!("{value:?}")),
+            "fields" => self.fields_field = Some(format!("{value:?}")),
+            _ => {}
+        }
+    }
+}
+
+impl<S> Layer<S> for Captor
+where
+    S: tracing::Subscriber,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        self.0.lock().unwrap().push(CapturedSpan {
+            span_name: attrs.metadata().name(),
+            name_field: visitor.name_field,
+            fields_field: visitor.fields_field,
+        });
+    }
+}
+
+// Pins the representation documented in `src/scope.rs`: `with_scope`'s dynamic key-value pairs
+// collapse into a single `fields` string on a span literally named "scope", with the logical
+// scope name carried separately in a `name` field - rather than becoming individually queryable
+// span fields.
+#[test]
+fn with_scope_emits_a_scope_span_with_a_collapsed_fields_string() {
+    let captor = Captor::default();
+    let subscriber = tracing_subscriber::registry().with(captor.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        with_scope("request", &[("request_id", "abc123"), ("tenant", "acme")], || {
+            tracing::info!("handling request");
+        });
+    });
+
+    let captured = captor.0.lock().unwrap();
+    let span = captured.iter().find(|s| s.span_name == "scope").expect("scope span should have been created");
+
+    assert_eq!(span.name_field.as_deref(), Some("request"));
+    let fields = span.fields_field.as_deref().expect("fields field should be present");
+    assert!(fields.contains("request_id=\"abc123\""), "got: {fields}");
+    assert!(fields.contains("tenant=\"acme\""), "got: {fields}");
+}
+
+#[tokio::test]
+async fn with_scope_async_propagates_the_same_shape_across_await_points() {
+    let captor = Captor::default();
+    let subscriber = tracing_subscriber::registry().with(captor.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    with_scope_async("job", &[("job_id", "42")], async {
+        tokio::task::yield_now().await;
+        tracing::info!("running job");
+    })
+    .await;
+
+    let captured = captor.0.lock().unwrap();
+    let span = captured.iter().find(|s| s.span_name == "scope").expect("scope span should have been created");
+    assert_eq!(span.name_field.as_deref(), Some("job"));
+    assert_eq!(span.fields_field.as_deref(), Some("job_id=\"42\""));
+}